@@ -6,8 +6,226 @@ pub trait Key: Clone + Eq + Hash + Debug {}
 
 pub trait Value: Clone + Debug {}
 
+/// Implemented by values that can report a cache weight (e.g. byte size),
+/// for use with `CacheUp::with_weight_limit`.
+pub trait Weight {
+    fn weight(&self) -> u64;
+}
+
+/// Implemented by values that know their own freshness, for use with
+/// `CacheOption::expire_on_value`.
+pub trait CanExpire {
+    fn is_expired(&self) -> bool;
+}
+
+/// Error returned when an entry cannot be admitted into a weight-limited
+/// `CacheUp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheUpError {
+    /// The value does not fit under `max_weight`: either its weight alone
+    /// exceeds `max_weight`, so no amount of eviction could make room for
+    /// it, or every remaining entry is refused by `EvictionPolicy::can_evict`
+    /// before enough room was freed. Entries already evicted on the way are
+    /// not restored.
+    ValueTooLarge { weight: u64, max_weight: u64 },
+}
+
+impl std::fmt::Display for CacheUpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheUpError::ValueTooLarge { weight, max_weight } => write!(
+                f,
+                "value weight {} does not fit under max_weight {}",
+                weight, max_weight
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CacheUpError {}
+
+/// Selects which entry to evict when a `CacheUp` is over its `max_weight`.
+/// The entry with the lowest `rank` is evicted first.
+pub trait EvictionPolicy<K: Key, V: Value> {
+    fn rank(&self, key: &K, value: &V, ctx: &CacheContext<K, V>) -> u64;
+
+    /// Returns `false` to keep `value` alive even if it is the lowest-ranked
+    /// entry, e.g. to pin it.
+    fn can_evict(&self, value: &V) -> bool {
+        let _ = value;
+        true
+    }
+}
+
+/// Evicts the least-recently-used entry first.
+pub struct LruPolicy;
+
+impl<K: Key, V: Value> EvictionPolicy<K, V> for LruPolicy {
+    fn rank(&self, _key: &K, _value: &V, ctx: &CacheContext<K, V>) -> u64 {
+        ctx.access_seq
+    }
+}
+
+/// Evicts the least-frequently-used entry first.
+pub struct LfuPolicy;
+
+impl<K: Key, V: Value> EvictionPolicy<K, V> for LfuPolicy {
+    fn rank(&self, _key: &K, _value: &V, ctx: &CacheContext<K, V>) -> u64 {
+        ctx.access_count
+    }
+}
+
+struct RecencyNode<K> {
+    key: K,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Intrusive doubly-linked list ordering keys from least- to
+/// most-recently-used, indexed by key for O(1)-amortized `touch`/`remove`.
+/// Backs `CacheUp`'s size-bounded LRU eviction; a `VecDeque` would need an
+/// O(n) scan to relocate a touched key.
+struct RecencyList<K: Key> {
+    nodes: Vec<RecencyNode<K>>,
+    index: HashMap<K, usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    free: Vec<usize>,
+}
+
+impl<K: Key> RecencyList<K> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            head: None,
+            tail: None,
+            free: Vec::new(),
+        }
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next) = {
+            let node = &self.nodes[idx];
+            (node.prev, node.next)
+        };
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn link_at_tail(&mut self, idx: usize) {
+        self.nodes[idx].prev = self.tail;
+        self.nodes[idx].next = None;
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(idx),
+            None => self.head = Some(idx),
+        }
+        self.tail = Some(idx);
+    }
+
+    /// Moves `key` to the most-recently-used end, inserting it if absent.
+    fn touch(&mut self, key: &K) {
+        if let Some(&idx) = self.index.get(key) {
+            if self.tail == Some(idx) {
+                return;
+            }
+            self.unlink(idx);
+            self.link_at_tail(idx);
+            return;
+        }
+
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = RecencyNode {
+                    key: key.clone(),
+                    prev: None,
+                    next: None,
+                };
+                idx
+            }
+            None => {
+                self.nodes.push(RecencyNode {
+                    key: key.clone(),
+                    prev: None,
+                    next: None,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        self.link_at_tail(idx);
+        self.index.insert(key.clone(), idx);
+    }
+
+    /// Removes `key`, if present.
+    fn remove(&mut self, key: &K) {
+        if let Some(idx) = self.index.remove(key) {
+            self.unlink(idx);
+            self.free.push(idx);
+        }
+    }
+
+    /// Removes and returns the least-recently-used key, if any.
+    fn pop_front(&mut self) -> Option<K> {
+        let idx = self.head?;
+        self.unlink(idx);
+        let key = self.nodes[idx].key.clone();
+        self.index.remove(&key);
+        self.free.push(idx);
+        Some(key)
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.index.clear();
+        self.head = None;
+        self.tail = None;
+        self.free.clear();
+    }
+}
+
 pub struct CacheUp<K: Key, V: Value> {
     store: HashMap<K, (V, CacheContext<K, V>)>,
+    max_size: Option<usize>,
+    recency: RecencyList<K>,
+    last_evicted: Option<(K, V)>,
+    hits: u64,
+    misses: u64,
+    max_weight: Option<u64>,
+    total_weight: u64,
+    #[allow(clippy::type_complexity)]
+    weigher: Option<Box<dyn Fn(&V) -> u64>>,
+    eviction_policy: Box<dyn EvictionPolicy<K, V>>,
+    /// Monotonic counter stamped onto `CacheContext::access_seq` on every
+    /// touch, so recency can be ordered without relying on clock resolution.
+    access_clock: u64,
+}
+
+/// Point-in-time hit/miss instrumentation for a `CacheUp`, returned by
+/// `CacheUp::stats`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+}
+
+impl CacheStats {
+    /// Ratio of hits to total lookups, in the range `0.0..=1.0`.
+    /// Returns `0.0` when there have been no lookups yet.
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
 }
 
 impl<K: Key, V: Value> Debug for CacheUp<K, V> {
@@ -29,6 +247,13 @@ impl<K: Key, V: Value> Default for CacheUp<K, V> {
 pub struct CacheContext<K: Key, V: Value> {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub last_accessed: DateTime<Utc>,
+    /// Number of times this entry has been accessed since it was inserted.
+    pub access_count: u64,
+    /// Value of the owning `CacheUp`'s monotonic access clock as of the last
+    /// touch; `LruPolicy` ranks by this alone, since `last_accessed` can tie
+    /// at clock resolution.
+    pub access_seq: u64,
     pub option: CacheOption<K, V>,
 }
 
@@ -68,16 +293,253 @@ impl<K: Key, V: Value> CacheOption<K, V> {
 
         diff_updated < max_age
     }
+
+    /// Refreshes the entry whenever the cached value itself reports
+    /// `CanExpire::is_expired`, e.g. a JWT carrying its own `exp` or an API
+    /// response with a server-supplied TTL. More expressive than `max_age`
+    /// when freshness is encoded in the payload rather than measured from
+    /// `updated_at`.
+    pub fn expire_on_value(self) -> Self
+    where
+        V: CanExpire,
+    {
+        self.add_policy(|_, value, _| value.is_expired())
+    }
 }
 
 impl<K: Key, V: Value> CacheUp<K, V> {
     pub fn new() -> CacheUp<K, V> {
         CacheUp {
             store: HashMap::new(),
+            max_size: None,
+            recency: RecencyList::new(),
+            last_evicted: None,
+            hits: 0,
+            misses: 0,
+            max_weight: None,
+            total_weight: 0,
+            weigher: None,
+            eviction_policy: Box::new(LruPolicy),
+            access_clock: 0,
+        }
+    }
+
+    /// Creates a `CacheUp` that evicts the least-recently-used entry once
+    /// `max_size` entries are stored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is `0`: `execute` returns a reference into the
+    /// just-inserted entry, so a cache that can never retain anything has
+    /// no valid value to hand back.
+    pub fn with_capacity(max_size: usize) -> CacheUp<K, V> {
+        assert!(max_size > 0, "CacheUp::with_capacity requires max_size > 0");
+        CacheUp {
+            store: HashMap::new(),
+            max_size: Some(max_size),
+            recency: RecencyList::new(),
+            last_evicted: None,
+            hits: 0,
+            misses: 0,
+            max_weight: None,
+            total_weight: 0,
+            weigher: None,
+            eviction_policy: Box::new(LruPolicy),
+            access_clock: 0,
+        }
+    }
+
+    /// Creates a `CacheUp` that evicts entries (lowest `EvictionPolicy::rank`
+    /// first, defaulting to LRU) once their combined `Weight::weight` would
+    /// exceed `max_weight`.
+    pub fn with_weight_limit(max_weight: u64) -> CacheUp<K, V>
+    where
+        V: Weight + 'static,
+    {
+        CacheUp {
+            store: HashMap::new(),
+            max_size: None,
+            recency: RecencyList::new(),
+            last_evicted: None,
+            hits: 0,
+            misses: 0,
+            max_weight: Some(max_weight),
+            total_weight: 0,
+            weigher: Some(Box::new(V::weight)),
+            eviction_policy: Box::new(LruPolicy),
+            access_clock: 0,
+        }
+    }
+
+    /// Overrides the victim-selection strategy used by a weight-limited
+    /// `CacheUp`. Has no effect unless `with_weight_limit` was used.
+    pub fn with_eviction_policy<P: EvictionPolicy<K, V> + 'static>(mut self, policy: P) -> Self {
+        self.eviction_policy = Box::new(policy);
+        self
+    }
+
+    /// Returns the entry evicted by the most recent `execute`/`execute_with_option`
+    /// call, if any, and clears it so it is only reported once. A
+    /// weight-limited cache can evict several entries in one call; only the
+    /// last of them is returned.
+    pub fn take_evicted(&mut self) -> Option<(K, V)> {
+        self.last_evicted.take()
+    }
+
+    /// Returns the hit/miss counters accumulated since creation or the last
+    /// `reset_stats` call.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            size: self.store.len(),
         }
     }
 
-    pub fn execute<F: Fn() -> V>(&mut self, key: K, f: F) -> &(V, CacheContext<K, V>) {
+    /// Zeroes the hit/miss counters without touching any cached entries.
+    pub fn reset_stats(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Removes `key`, if present, without waiting for its policy to fire on
+    /// the next `execute`.
+    pub fn invalidate(&mut self, key: &K) {
+        if let Some((value, _)) = self.store.remove(key) {
+            self.total_weight -= self.weight_of(&value);
+        }
+        self.recency.remove(key);
+    }
+
+    /// Removes every entry.
+    pub fn invalidate_all(&mut self) {
+        self.store.clear();
+        self.recency.clear();
+        self.total_weight = 0;
+    }
+
+    /// Removes every entry whose policies currently report that it should
+    /// refresh, instead of waiting for the next `execute` on that key.
+    ///
+    /// An entry is stale as soon as *any one* policy fires, matching the
+    /// refresh check `execute`/`execute_with_option` already run on a hit —
+    /// a cache with both `max_age` and `expire_on_value` should refresh on
+    /// whichever condition trips first, not wait for both at once.
+    pub fn invalidate_stale(&mut self) {
+        let stale_keys: Vec<K> = self
+            .store
+            .iter()
+            .filter(|(key, (value, ctx))| {
+                ctx.option
+                    .policies
+                    .iter()
+                    .any(|policy| policy(key, value, ctx))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in &stale_keys {
+            self.invalidate(key);
+        }
+    }
+
+    /// Returns `true` if `key` is cached, without recomputing or affecting
+    /// stats or recency.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.store.contains_key(key)
+    }
+
+    /// Returns the cached value for `key`, if present, without recomputing
+    /// or affecting stats or recency.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.store.get(key).map(|(value, _)| value)
+    }
+
+    fn touch_recency(&mut self, key: &K) {
+        if self.max_size.is_none() {
+            return;
+        }
+        self.recency.touch(key);
+    }
+
+    fn evict_if_full(&mut self) {
+        let max_size = match self.max_size {
+            Some(max_size) => max_size,
+            None => return,
+        };
+        if self.store.len() < max_size {
+            return;
+        }
+        if let Some(victim) = self.recency.pop_front() {
+            if let Some((value, _)) = self.store.remove(&victim) {
+                self.last_evicted = Some((victim, value));
+            }
+        }
+    }
+
+    fn weight_of(&self, value: &V) -> u64 {
+        self.weigher.as_ref().map_or(0, |weigher| weigher(value))
+    }
+
+    /// Evicts entries, lowest `eviction_policy` rank first, until `needed`
+    /// additional weight fits under `max_weight`. `protect` is never chosen
+    /// as a victim, e.g. the entry currently being refreshed.
+    fn make_room_for_weight(
+        &mut self,
+        needed: u64,
+        protect: Option<&K>,
+    ) -> Result<(), CacheUpError> {
+        let max_weight = match self.max_weight {
+            Some(max_weight) => max_weight,
+            None => return Ok(()),
+        };
+        if needed > max_weight {
+            return Err(CacheUpError::ValueTooLarge {
+                weight: needed,
+                max_weight,
+            });
+        }
+
+        while self.total_weight + needed > max_weight {
+            let mut victim: Option<(K, u64)> = None;
+            for (candidate_key, (candidate_value, candidate_ctx)) in self.store.iter() {
+                if protect == Some(candidate_key)
+                    || !self.eviction_policy.can_evict(candidate_value)
+                {
+                    continue;
+                }
+                let rank = self
+                    .eviction_policy
+                    .rank(candidate_key, candidate_value, candidate_ctx);
+                if victim.as_ref().is_none_or(|(_, best)| rank < *best) {
+                    victim = Some((candidate_key.clone(), rank));
+                }
+            }
+
+            let victim = match victim {
+                Some((victim_key, _)) => victim_key,
+                None => {
+                    return Err(CacheUpError::ValueTooLarge {
+                        weight: needed,
+                        max_weight,
+                    })
+                }
+            };
+
+            if let Some((value, _)) = self.store.remove(&victim) {
+                self.total_weight -= self.weight_of(&value);
+                self.last_evicted = Some((victim, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn execute<F: Fn() -> V>(
+        &mut self,
+        key: K,
+        f: F,
+    ) -> Result<&(V, CacheContext<K, V>), CacheUpError> {
         self.execute_with_option(key, f, CacheOption::<K, V>::new())
     }
 
@@ -86,29 +548,187 @@ impl<K: Key, V: Value> CacheUp<K, V> {
         key: K,
         f: F,
         option: CacheOption<K, V>,
-    ) -> &(V, CacheContext<K, V>) {
-        self.store
-            .entry(key.clone())
-            .and_modify(|item| {
-                for policy in &item.1.option.policies {
-                    if policy(&key, &item.0, &item.1) {
-                        item.0 = f();
-                        item.1.updated_at = Utc::now();
-                        break;
-                    }
+    ) -> Result<&(V, CacheContext<K, V>), CacheUpError> {
+        self.last_evicted = None;
+        self.access_clock += 1;
+        let access_seq = self.access_clock;
+
+        let is_new_key = !self.store.contains_key(&key);
+        if is_new_key {
+            self.evict_if_full();
+        }
+        self.touch_recency(&key);
+
+        let mut recomputed = false;
+
+        if is_new_key {
+            recomputed = true;
+            let value = f();
+            let weight = self.weight_of(&value);
+            self.make_room_for_weight(weight, None)?;
+            self.total_weight += weight;
+
+            let created_at = Utc::now();
+            let cache_context = CacheContext {
+                created_at,
+                updated_at: created_at,
+                last_accessed: created_at,
+                access_count: 0,
+                access_seq,
+                option,
+            };
+            self.store.insert(key.clone(), (value, cache_context));
+        } else {
+            let now = Utc::now();
+            let old_weight = self.weight_of(&self.store[&key].0);
+
+            let should_refresh = {
+                let item = self.store.get_mut(&key).expect("checked by is_new_key");
+                item.1.last_accessed = now;
+                item.1.access_count += 1;
+                item.1.access_seq = access_seq;
+                item.1
+                    .option
+                    .policies
+                    .iter()
+                    .any(|policy| policy(&key, &item.0, &item.1))
+            };
+
+            if should_refresh {
+                recomputed = true;
+                let new_value = f();
+                let new_weight = self.weight_of(&new_value);
+
+                // Size room against the full new weight, not the delta, so an
+                // oversized refresh is rejected up front instead of evicting
+                // neighbors on the way to a `ValueTooLarge` anyway. The old
+                // entry's weight is excluded from `total_weight` first since
+                // it's about to be replaced, and `protect` keeps it from
+                // being evicted out from under itself in the meantime.
+                self.total_weight -= old_weight;
+                if let Err(err) = self.make_room_for_weight(new_weight, Some(&key)) {
+                    self.total_weight += old_weight;
+                    return Err(err);
                 }
-            })
-            .or_insert_with(|| {
-                let result = f();
-                let created_at = Utc::now();
-                let cache_context = CacheContext {
-                    created_at,
-                    updated_at: created_at,
-                    option,
-                };
+                self.total_weight += new_weight;
 
-                (result, cache_context)
-            })
+                let item = self.store.get_mut(&key).expect("checked by is_new_key");
+                item.0 = new_value;
+                item.1.updated_at = now;
+            }
+        }
+
+        if recomputed {
+            self.misses += 1;
+        } else {
+            self.hits += 1;
+        }
+
+        Ok(self.store.get(&key).expect("entry present"))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<K: Key, V: Value> CacheUp<K, V> {
+    /// Async counterpart of `execute` for producers that resolve via a
+    /// `Future`, e.g. network or database calls. The future is only polled
+    /// on a miss or policy-triggered refresh, mirroring `execute`.
+    pub async fn execute_async<F, Fut>(
+        &mut self,
+        key: K,
+        f: F,
+    ) -> Result<&(V, CacheContext<K, V>), CacheUpError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = V>,
+    {
+        self.execute_with_option_async(key, f, CacheOption::<K, V>::new())
+            .await
+    }
+
+    /// Async counterpart of `execute_with_option`.
+    pub async fn execute_with_option_async<F, Fut>(
+        &mut self,
+        key: K,
+        f: F,
+        option: CacheOption<K, V>,
+    ) -> Result<&(V, CacheContext<K, V>), CacheUpError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = V>,
+    {
+        self.last_evicted = None;
+        self.access_clock += 1;
+        let access_seq = self.access_clock;
+
+        let is_new_key = !self.store.contains_key(&key);
+        if is_new_key {
+            self.evict_if_full();
+        }
+        self.touch_recency(&key);
+
+        let mut recomputed = false;
+
+        if is_new_key {
+            recomputed = true;
+            let value = f().await;
+            let weight = self.weight_of(&value);
+            self.make_room_for_weight(weight, None)?;
+            self.total_weight += weight;
+
+            let created_at = Utc::now();
+            let cache_context = CacheContext {
+                created_at,
+                updated_at: created_at,
+                last_accessed: created_at,
+                access_count: 0,
+                access_seq,
+                option,
+            };
+            self.store.insert(key.clone(), (value, cache_context));
+        } else {
+            let now = Utc::now();
+            let old_weight = self.weight_of(&self.store[&key].0);
+
+            let should_refresh = {
+                let item = self.store.get_mut(&key).expect("checked by is_new_key");
+                item.1.last_accessed = now;
+                item.1.access_count += 1;
+                item.1.access_seq = access_seq;
+                item.1
+                    .option
+                    .policies
+                    .iter()
+                    .any(|policy| policy(&key, &item.0, &item.1))
+            };
+
+            if should_refresh {
+                recomputed = true;
+                let new_value = f().await;
+                let new_weight = self.weight_of(&new_value);
+
+                // See the sync `execute_with_option` for why room is sized
+                // against the full new weight rather than the delta.
+                self.total_weight -= old_weight;
+                if let Err(err) = self.make_room_for_weight(new_weight, Some(&key)) {
+                    self.total_weight += old_weight;
+                    return Err(err);
+                }
+                self.total_weight += new_weight;
+
+                let item = self.store.get_mut(&key).expect("checked by is_new_key");
+                item.0 = new_value;
+                item.1.updated_at = now;
+            }
+        }
+
+        if recomputed {
+            self.misses += 1;
+        } else {
+            self.hits += 1;
+        }
+
+        Ok(self.store.get(&key).expect("entry present"))
     }
 }
 
@@ -122,16 +742,16 @@ mod tests {
         impl Value for i64 {}
 
         let mut cache_up = CacheUp::<i64, i64>::new();
-        let (result, _) = cache_up.execute(1, || 2 + 2);
+        let (result, _) = cache_up.execute(1, || 2 + 2).unwrap();
         assert_eq!(result, &4);
 
-        let (result, _) = cache_up.execute(1, || 5 + 5);
+        let (result, _) = cache_up.execute(1, || 5 + 5).unwrap();
         assert_eq!(result, &4);
 
-        let (result, _) = cache_up.execute(2, || 5 + 5);
+        let (result, _) = cache_up.execute(2, || 5 + 5).unwrap();
         assert_eq!(result, &10);
 
-        let (result, _) = cache_up.execute(2, || 6 + 6);
+        let (result, _) = cache_up.execute(2, || 6 + 6).unwrap();
         assert_eq!(result, &10);
     }
 
@@ -147,16 +767,18 @@ mod tests {
         impl Key for String {}
 
         let mut cache_up = CacheUp::<String, Test>::new();
-        let (result, _) = cache_up.execute("aaa".to_string(), || Test::A);
+        let (result, _) = cache_up.execute("aaa".to_string(), || Test::A).unwrap();
         assert_eq!(result, &Test::A);
 
-        let (result, _) = cache_up.execute("aaa".to_string(), || Test::B);
+        let (result, _) = cache_up.execute("aaa".to_string(), || Test::B).unwrap();
         assert_eq!(result, &Test::A);
 
-        let (result, _) = cache_up.execute("bbb".to_string(), || Test::B);
+        let (result, _) = cache_up.execute("bbb".to_string(), || Test::B).unwrap();
         assert_eq!(result, &Test::B);
 
-        let (result, _) = cache_up.execute("ccc".to_string(), || Test::C("inner_ccc".to_string()));
+        let (result, _) = cache_up
+            .execute("ccc".to_string(), || Test::C("inner_ccc".to_string()))
+            .unwrap();
         assert_eq!(result, &Test::C("inner_ccc".to_string()));
     }
 
@@ -164,18 +786,266 @@ mod tests {
     fn it_works_with_option() {
         let mut cache_up = CacheUp::<i64, i64>::new();
         let cache_opt = CacheOption::new().add_policy(|_, _, _| true);
-        let (result, _) = cache_up.execute_with_option(1, || 2 + 2, cache_opt);
+        let (result, _) = cache_up
+            .execute_with_option(1, || 2 + 2, cache_opt)
+            .unwrap();
         assert_eq!(result, &4);
 
-        let (result, _) = cache_up.execute(1, || 5 + 5);
+        let (result, _) = cache_up.execute(1, || 5 + 5).unwrap();
         assert_eq!(result, &10);
 
         let mut cache_up = CacheUp::<i64, i64>::new();
         let cache_opt = CacheOption::new().add_policy(|_, _, _| false);
-        let (result, _) = cache_up.execute_with_option(1, || 2 + 2, cache_opt);
+        let (result, _) = cache_up
+            .execute_with_option(1, || 2 + 2, cache_opt)
+            .unwrap();
+        assert_eq!(result, &4);
+
+        let (result, _) = cache_up.execute(1, || 5 + 5).unwrap();
+        assert_eq!(result, &4);
+    }
+
+    #[test]
+    fn it_evicts_least_recently_used_when_full() {
+        let mut cache_up = CacheUp::<i64, i64>::with_capacity(2);
+
+        cache_up.execute(1, || 10).unwrap();
+        cache_up.execute(2, || 20).unwrap();
+        assert_eq!(cache_up.take_evicted(), None);
+
+        // touch `1` so `2` becomes the least recently used entry.
+        cache_up.execute(1, || 10).unwrap();
+
+        cache_up.execute(3, || 30).unwrap();
+        assert_eq!(cache_up.take_evicted(), Some((2, 20)));
+        assert!(!cache_up.store.contains_key(&2));
+        assert!(cache_up.store.contains_key(&1));
+        assert!(cache_up.store.contains_key(&3));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_size > 0")]
+    fn it_rejects_zero_capacity() {
+        CacheUp::<i64, i64>::with_capacity(0);
+    }
+
+    #[test]
+    fn it_tracks_hit_and_miss_stats() {
+        let mut cache_up = CacheUp::<i64, i64>::new();
+
+        cache_up.execute(1, || 2 + 2).unwrap();
+        cache_up.execute(1, || 5 + 5).unwrap();
+        cache_up.execute(2, || 5 + 5).unwrap();
+
+        let stats = cache_up.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.size, 2);
+        assert_eq!(stats.hit_ratio(), 1.0 / 3.0);
+
+        cache_up.reset_stats();
+        let stats = cache_up.stats();
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 0);
+        assert_eq!(stats.size, 2);
+    }
+
+    #[test]
+    fn it_evicts_by_weight_using_lru() {
+        impl Weight for i64 {
+            fn weight(&self) -> u64 {
+                1
+            }
+        }
+
+        let mut cache_up = CacheUp::<i64, i64>::with_weight_limit(2);
+
+        cache_up.execute(1, || 10).unwrap();
+        cache_up.execute(2, || 20).unwrap();
+        // touch `1` so `2` becomes the least recently used entry.
+        cache_up.execute(1, || 10).unwrap();
+
+        cache_up.execute(3, || 30).unwrap();
+        assert_eq!(cache_up.take_evicted(), Some((2, 20)));
+        assert!(!cache_up.store.contains_key(&2));
+    }
+
+    #[test]
+    fn it_evicts_by_weight_using_lfu() {
+        let mut cache_up =
+            CacheUp::<i64, i64>::with_weight_limit(2).with_eviction_policy(LfuPolicy);
+
+        cache_up.execute(1, || 10).unwrap();
+        cache_up.execute(2, || 20).unwrap();
+        // access `2` again so `1` becomes the least-frequently-used entry.
+        cache_up.execute(2, || 20).unwrap();
+
+        cache_up.execute(3, || 30).unwrap();
+        assert_eq!(cache_up.take_evicted(), Some((1, 10)));
+        assert!(!cache_up.store.contains_key(&1));
+    }
+
+    #[test]
+    fn it_errors_when_a_single_value_exceeds_max_weight() {
+        impl Value for String {}
+        impl Weight for String {
+            fn weight(&self) -> u64 {
+                self.len() as u64
+            }
+        }
+
+        let mut cache_up = CacheUp::<i64, String>::with_weight_limit(3);
+
+        let (result, _) = cache_up.execute(1, || "ab".to_string()).unwrap();
+        assert_eq!(result, "ab");
+
+        let err = match cache_up.execute(2, || "too long".to_string()) {
+            Err(err) => err,
+            Ok(_) => panic!("expected an oversized-value error"),
+        };
+        assert_eq!(
+            err,
+            CacheUpError::ValueTooLarge {
+                weight: 8,
+                max_weight: 3
+            }
+        );
+        // the oversized insert was rejected, the existing entry survives.
+        assert!(cache_up.store.contains_key(&1));
+        assert!(!cache_up.store.contains_key(&2));
+    }
+
+    #[test]
+    fn it_errors_when_a_refresh_would_exceed_max_weight() {
+        let mut cache_up = CacheUp::<i64, String>::with_weight_limit(3);
+        let always_stale = || CacheOption::new().add_policy(|_, _, _| true);
+
+        cache_up
+            .execute_with_option(1, || "a".to_string(), always_stale())
+            .unwrap();
+        cache_up
+            .execute_with_option(2, || "b".to_string(), always_stale())
+            .unwrap();
+
+        // refreshing `1` to an oversized value must fail without disturbing
+        // `2`, which a delta-sized room check could wrongly evict first.
+        let err = match cache_up.execute_with_option(1, || "too long".to_string(), always_stale()) {
+            Err(err) => err,
+            Ok(_) => panic!("expected an oversized-value error"),
+        };
+        assert_eq!(
+            err,
+            CacheUpError::ValueTooLarge {
+                weight: 8,
+                max_weight: 3
+            }
+        );
+        assert_eq!(cache_up.take_evicted(), None);
+        assert_eq!(cache_up.peek(&1), Some(&"a".to_string()));
+        assert_eq!(cache_up.peek(&2), Some(&"b".to_string()));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn it_only_awaits_the_producer_on_a_miss() {
+        let mut cache_up = CacheUp::<i64, i64>::new();
+
+        let (result, _) =
+            futures::executor::block_on(cache_up.execute_async(1, || async { 2 + 2 })).unwrap();
         assert_eq!(result, &4);
 
-        let (result, _) = cache_up.execute(1, || 5 + 5);
+        let (result, _) =
+            futures::executor::block_on(cache_up.execute_async(1, || async { 5 + 5 })).unwrap();
         assert_eq!(result, &4);
+
+        let stats = cache_up.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[test]
+    fn it_refreshes_when_the_value_reports_expired() {
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        struct Token {
+            value: i64,
+            expired: bool,
+        }
+        impl Value for Token {}
+        impl CanExpire for Token {
+            fn is_expired(&self) -> bool {
+                self.expired
+            }
+        }
+
+        let mut cache_up = CacheUp::<i64, Token>::new();
+        let cache_opt = CacheOption::new().expire_on_value();
+
+        let (result, _) = cache_up
+            .execute_with_option(
+                1,
+                || Token {
+                    value: 1,
+                    expired: true,
+                },
+                cache_opt,
+            )
+            .unwrap();
+        assert_eq!(result.value, 1);
+        assert!(result.is_expired());
+
+        let (result, _) = cache_up
+            .execute(1, || Token {
+                value: 2,
+                expired: false,
+            })
+            .unwrap();
+        assert_eq!(result.value, 2);
+        assert!(!result.is_expired());
+
+        let (result, _) = cache_up
+            .execute(1, || Token {
+                value: 3,
+                expired: false,
+            })
+            .unwrap();
+        assert_eq!(result.value, 2);
+        assert!(!result.is_expired());
+    }
+
+    #[test]
+    fn it_supports_explicit_invalidation() {
+        let mut cache_up = CacheUp::<i64, i64>::new();
+
+        cache_up.execute(1, || 10).unwrap();
+        cache_up.execute(2, || 20).unwrap();
+
+        assert!(cache_up.contains_key(&1));
+        assert_eq!(cache_up.peek(&1), Some(&10));
+
+        cache_up.invalidate(&1);
+        assert!(!cache_up.contains_key(&1));
+        assert_eq!(cache_up.peek(&1), None);
+        assert!(cache_up.contains_key(&2));
+
+        cache_up.invalidate_all();
+        assert!(!cache_up.contains_key(&2));
+        assert_eq!(cache_up.stats().size, 0);
+    }
+
+    #[test]
+    fn it_invalidates_stale_entries_without_waiting_for_access() {
+        let mut cache_up = CacheUp::<i64, i64>::new();
+
+        let always_stale = CacheOption::new().add_policy(|_, _, _| true);
+        let never_stale = CacheOption::new().add_policy(|_, _, _| false);
+
+        cache_up
+            .execute_with_option(1, || 10, always_stale)
+            .unwrap();
+        cache_up.execute_with_option(2, || 20, never_stale).unwrap();
+
+        cache_up.invalidate_stale();
+        assert!(!cache_up.contains_key(&1));
+        assert!(cache_up.contains_key(&2));
     }
 }